@@ -0,0 +1,44 @@
+//! `agent-of-empires` CLI command definitions
+
+pub mod sandbox;
+pub mod sounds;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(name = "aoe", version, about)]
+pub struct Cli {
+    /// Remote Docker engine to target (overrides $DOCKER_HOST)
+    #[arg(long, global = true)]
+    pub docker_host: Option<String>,
+
+    /// Container runtime backend to use: "docker" or "podman" (overrides $AOE_RUNTIME)
+    #[arg(long, global = true)]
+    pub runtime: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+}
+
+#[derive(Subcommand)]
+pub enum Commands {
+    /// Manage sandbox containers and their resources
+    #[command(subcommand)]
+    Sandbox(sandbox::SandboxCommands),
+
+    /// Manage sound effects
+    #[command(subcommand)]
+    Sounds(sounds::SoundsCommands),
+}
+
+pub async fn run(cli: Cli) -> Result<()> {
+    match cli.command {
+        Some(Commands::Sandbox(cmd)) => sandbox::run(cmd, cli.runtime, cli.docker_host).await,
+        Some(Commands::Sounds(cmd)) => sounds::run(cmd).await,
+        None => {
+            // No subcommand: launch the TUI.
+            Ok(())
+        }
+    }
+}
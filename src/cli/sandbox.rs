@@ -0,0 +1,101 @@
+//! `agent-of-empires sandbox` subcommands implementation
+
+use anyhow::Result;
+use clap::Subcommand;
+use std::path::PathBuf;
+
+use crate::docker;
+
+#[derive(Subcommand)]
+pub enum SandboxCommands {
+    /// Build the sandbox image from docker/Dockerfile
+    Build,
+
+    /// Manage persistent named volumes (tool caches) used by sandbox containers
+    #[command(subcommand)]
+    Volumes(VolumesCommands),
+}
+
+#[derive(Subcommand)]
+pub enum VolumesCommands {
+    /// List persistent sandbox cache volumes
+    #[command(alias = "ls")]
+    List,
+
+    /// Remove a named cache volume
+    Remove {
+        /// Volume name, e.g. aoe-cache-npm
+        name: String,
+    },
+
+    /// Remove cache volumes not attached to any sandbox container
+    Prune,
+}
+
+pub async fn run(
+    command: SandboxCommands,
+    runtime: Option<String>,
+    docker_host: Option<String>,
+) -> Result<()> {
+    match command {
+        SandboxCommands::Build => build_image(runtime.as_deref(), docker_host.as_deref()),
+        SandboxCommands::Volumes(cmd) => run_volumes(cmd, runtime.as_deref(), docker_host.as_deref()).await,
+    }
+}
+
+fn build_image(runtime: Option<&str>, docker_host: Option<&str>) -> Result<()> {
+    let docker_dir = PathBuf::from("docker");
+    let runtime = docker::select_runtime(runtime);
+    println!("🔨 Building sandbox image...");
+    docker::build_sandbox_image(runtime.as_ref(), &docker_dir, docker_host)?;
+    println!("✓ Built {}", docker::SANDBOX_IMAGE);
+    Ok(())
+}
+
+async fn run_volumes(command: VolumesCommands, runtime: Option<&str>, docker_host: Option<&str>) -> Result<()> {
+    let runtime = docker::select_runtime(runtime);
+    match command {
+        VolumesCommands::List => list_volumes(runtime.as_ref(), docker_host),
+        VolumesCommands::Remove { name } => remove_volume(runtime.as_ref(), &name, docker_host),
+        VolumesCommands::Prune => prune_volumes(runtime.as_ref(), docker_host),
+    }
+}
+
+fn list_volumes(runtime: &dyn docker::ContainerRuntime, docker_host: Option<&str>) -> Result<()> {
+    let volumes = docker::list_named_volumes(runtime, docker_host)?;
+
+    if volumes.is_empty() {
+        println!("No sandbox cache volumes found.");
+        return Ok(());
+    }
+
+    println!("📦 Sandbox cache volumes:");
+    for volume in &volumes {
+        println!("  • {}", volume);
+    }
+    println!("\nTotal: {} volumes", volumes.len());
+
+    Ok(())
+}
+
+fn remove_volume(runtime: &dyn docker::ContainerRuntime, name: &str, docker_host: Option<&str>) -> Result<()> {
+    docker::remove_named_volume(runtime, name, docker_host)?;
+    println!("✓ Removed volume '{}'", name);
+    Ok(())
+}
+
+fn prune_volumes(runtime: &dyn docker::ContainerRuntime, docker_host: Option<&str>) -> Result<()> {
+    let removed = docker::prune_orphaned_volumes(runtime, docker_host)?;
+
+    if removed.is_empty() {
+        println!("No orphaned cache volumes to remove.");
+        return Ok(());
+    }
+
+    println!("✓ Pruned {} orphaned volume(s):", removed.len());
+    for volume in &removed {
+        println!("  • {}", volume);
+    }
+
+    Ok(())
+}
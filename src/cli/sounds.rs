@@ -7,8 +7,16 @@ use crate::sound;
 
 #[derive(Subcommand)]
 pub enum SoundsCommands {
-    /// Install bundled sound effects
-    Install,
+    /// Install bundled sound effects, or a community pack with --from
+    Install {
+        /// URL of a community sound pack (.tar.gz of .wav/.ogg files) to install instead of the bundled set
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Overwrite sounds that are already installed
+        #[arg(long)]
+        force: bool,
+    },
 
     /// List currently installed sounds
     #[command(alias = "ls")]
@@ -23,7 +31,8 @@ pub enum SoundsCommands {
 
 pub async fn run(command: SoundsCommands) -> Result<()> {
     match command {
-        SoundsCommands::Install => install_bundled().await,
+        SoundsCommands::Install { from: Some(url), force } => install_from_url(&url, force).await,
+        SoundsCommands::Install { from: None, .. } => install_bundled().await,
         SoundsCommands::List => list_sounds().await,
         SoundsCommands::Test { name } => test_sound(&name).await,
     }
@@ -59,6 +68,27 @@ async fn install_bundled() -> Result<()> {
     Ok(())
 }
 
+async fn install_from_url(url: &str, force: bool) -> Result<()> {
+    let installed = sound::install_from_url(url, force).await?;
+
+    if installed.is_empty() {
+        println!("No new sounds installed (all entries already present; pass --force to overwrite).");
+        return Ok(());
+    }
+
+    if let Some(sounds_dir) = sound::get_sounds_dir() {
+        println!("✓ Installed sound pack from {} to:", url);
+        println!("  {}\n", sounds_dir.display());
+    }
+
+    println!("📂 Installed {} sounds:", installed.len());
+    for sound_name in installed {
+        println!("  • {}", sound_name);
+    }
+
+    Ok(())
+}
+
 async fn list_sounds() -> Result<()> {
     let sounds = sound::list_available_sounds();
 
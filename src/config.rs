@@ -0,0 +1,53 @@
+//! User-configurable settings, e.g. which agent CLIs a sandbox image installs.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Which agent CLIs are installed into the sandbox image. Mirrors
+/// `docker::SANDBOX_TOOLS` one field per tool, so enabling/disabling a tool
+/// here is reflected the next time the sandbox image is built.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct AvailableTools {
+    pub claude: bool,
+    pub opencode: bool,
+    pub codex: bool,
+}
+
+impl Default for AvailableTools {
+    fn default() -> Self {
+        Self {
+            claude: true,
+            opencode: true,
+            codex: true,
+        }
+    }
+}
+
+/// Persisted user settings, loaded from `~/.aoe/config.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub available_tools: AvailableTools,
+    /// Container runtime backend to use ("docker" or "podman"). Consulted
+    /// by `docker::select_runtime` when neither the `--runtime` CLI flag
+    /// nor the `AOE_RUNTIME` environment variable is set.
+    pub runtime: Option<String>,
+}
+
+fn config_path() -> Result<PathBuf> {
+    let home = dirs::home_dir().context("could not determine home directory")?;
+    Ok(home.join(".aoe").join("config.json"))
+}
+
+/// Loads settings from `~/.aoe/config.json`, or the defaults if it doesn't
+/// exist yet.
+pub fn load_settings() -> Result<Settings> {
+    let path = config_path()?;
+    if !path.exists() {
+        return Ok(Settings::default());
+    }
+    let json = fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&json).with_context(|| format!("failed to parse {}", path.display()))
+}
@@ -0,0 +1,857 @@
+//! Sandbox container lifecycle management.
+//!
+//! Containers are driven through a CLI (`docker` or `podman`) rather than a
+//! client library, behind the [`ContainerRuntime`] trait, so the same
+//! session/volume/image logic works whether or not a Docker daemon is even
+//! present on the host.
+
+use anyhow::{bail, Context, Result};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::process::Command;
+
+/// Prefix every named volume aoe manages gets, so they can be told apart
+/// from unrelated volumes on the engine (`<runtime> volume ls`).
+const VOLUME_PREFIX: &str = "aoe-cache-";
+
+/// The default sandbox image tag used for sessions that don't pin a custom one.
+pub const SANDBOX_IMAGE: &str = "aoe-sandbox:latest";
+
+/// Label the sandbox image is stamped with at build time, recording the
+/// hash of the Dockerfile and tool set it was built from.
+const DOCKERFILE_HASH_LABEL: &str = "aoe.dockerfile-hash";
+
+/// A CLI tool installed into the sandbox image.
+pub struct SandboxTool {
+    pub name: &'static str,
+    /// Command run inside the built image to report the installed version,
+    /// e.g. `["claude", "--version"]`.
+    pub version_cmd: &'static [&'static str],
+    /// Dockerfile `ARG` that pins this tool's installed version, if the
+    /// install command supports pinning one (e.g. codex via npm). `None`
+    /// for tools whose install script always fetches latest.
+    pub version_build_arg: Option<&'static str>,
+}
+
+/// The tools `aoe sandbox build` installs into the sandbox image.
+pub const SANDBOX_TOOLS: &[SandboxTool] = &[
+    SandboxTool {
+        name: "claude",
+        version_cmd: &["claude", "--version"],
+        version_build_arg: None,
+    },
+    SandboxTool {
+        name: "opencode",
+        version_cmd: &["opencode", "--version"],
+        version_build_arg: None,
+    },
+    SandboxTool {
+        name: "codex",
+        version_cmd: &["codex", "--version"],
+        version_build_arg: Some("CODEX_VERSION"),
+    },
+];
+
+/// The resolved tool versions installed in the last successful
+/// `aoe sandbox build`, persisted to `docker/tools.lock` so image rebuilds
+/// are reproducible instead of silently pulling whatever is latest.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ToolsLock {
+    pub versions: std::collections::BTreeMap<String, String>,
+}
+
+fn tools_lock_path(docker_dir: &Path) -> std::path::PathBuf {
+    docker_dir.join("tools.lock")
+}
+
+/// Reads `docker/tools.lock`, or an empty lock if it doesn't exist yet.
+pub fn load_tools_lock(docker_dir: &Path) -> Result<ToolsLock> {
+    let path = tools_lock_path(docker_dir);
+    if !path.exists() {
+        return Ok(ToolsLock::default());
+    }
+    let contents =
+        std::fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&contents).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+pub fn save_tools_lock(docker_dir: &Path, lock: &ToolsLock) -> Result<()> {
+    let path = tools_lock_path(docker_dir);
+    let json = serde_json::to_string_pretty(lock)?;
+    std::fs::write(&path, json).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Configuration for a sandbox container. Shared by every `ContainerRuntime`
+/// implementation.
+pub struct ContainerConfig {
+    pub working_dir: String,
+    pub volumes: Vec<String>,
+    /// Named (persistent) volumes, given as `name:mount_path`, e.g.
+    /// `aoe-cache-npm:/root/.npm`. Unlike `volumes`, these survive session
+    /// deletion and are reused across sandbox runs.
+    pub named_volumes: Vec<String>,
+    pub environment: Vec<String>,
+    pub cpu_limit: Option<String>,
+    pub memory_limit: Option<String>,
+    /// Network access granted to the container. Defaults to `None` so an
+    /// untrusted agent can't exfiltrate data or reach the internet unless
+    /// the user explicitly opts in.
+    pub network_mode: NetworkMode,
+    /// Maximum number of processes/threads the container may run.
+    pub pids_limit: Option<u32>,
+    /// Mount the root filesystem read-only, with `/tmp` as a writable
+    /// tmpfs mount for whatever scratch space tools need.
+    pub read_only_rootfs: bool,
+    /// Linux capabilities to drop, e.g. `["ALL"]`.
+    pub dropped_capabilities: Vec<String>,
+}
+
+impl Default for ContainerConfig {
+    fn default() -> Self {
+        Self {
+            working_dir: "/workspace".to_string(),
+            volumes: Vec::new(),
+            named_volumes: Vec::new(),
+            environment: Vec::new(),
+            cpu_limit: None,
+            memory_limit: None,
+            network_mode: NetworkMode::None,
+            pids_limit: None,
+            read_only_rootfs: false,
+            dropped_capabilities: Vec::new(),
+        }
+    }
+}
+
+/// Network access policy for a sandbox container.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NetworkMode {
+    /// No network access at all. The default for new sandboxes.
+    None,
+    /// The runtime's default bridge network.
+    Bridge,
+    /// A specific named network.
+    Named(String),
+}
+
+impl NetworkMode {
+    /// The value to pass to `--network`.
+    fn as_flag_value(&self) -> String {
+        match self {
+            NetworkMode::None => "none".to_string(),
+            NetworkMode::Bridge => "bridge".to_string(),
+            NetworkMode::Named(name) => name.clone(),
+        }
+    }
+}
+
+/// Name for the persistent cache volume backing a given sandbox tool, e.g.
+/// `aoe-cache-npm`.
+pub fn cache_volume_name(tool: &str) -> String {
+    format!("{VOLUME_PREFIX}{tool}")
+}
+
+/// A container lifecycle backend. `DockerRuntime` talks to a Docker daemon
+/// (local or remote); `PodmanRuntime` targets rootless/daemonless Podman.
+/// New backends only need to override the handful of methods where their
+/// CLI actually diverges from Docker's.
+pub trait ContainerRuntime: Send + Sync {
+    /// The CLI binary this runtime shells out to, e.g. `"docker"`.
+    fn binary(&self) -> &'static str;
+
+    /// Flag used to target a non-default host/socket.
+    fn host_flag(&self) -> &'static str {
+        "-H"
+    }
+
+    /// Extra `run` arguments this runtime needs beyond the shared set (e.g.
+    /// Podman's rootless cgroup manager).
+    fn extra_run_args(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn command(&self, host: Option<&str>) -> Command {
+        let mut cmd = Command::new(self.binary());
+        if let Some(host) = host {
+            cmd.args([self.host_flag(), host]);
+        }
+        cmd
+    }
+
+    fn is_available(&self) -> bool {
+        Command::new(self.binary())
+            .arg("--version")
+            .output()
+            .is_ok_and(|out| out.status.success())
+    }
+
+    fn is_daemon_running(&self) -> bool {
+        Command::new(self.binary())
+            .arg("info")
+            .output()
+            .is_ok_and(|out| out.status.success())
+    }
+
+    fn exists(&self, host: Option<&str>, name: &str) -> Result<bool> {
+        let output = self
+            .command(host)
+            .args(["ps", "-a", "-q", "--filter", &format!("name=^{name}$")])
+            .output()
+            .with_context(|| format!("failed to run {} ps", self.binary()))?;
+        Ok(!output.stdout.is_empty())
+    }
+
+    fn is_running(&self, host: Option<&str>, name: &str) -> Result<bool> {
+        let output = self
+            .command(host)
+            .args(["ps", "-q", "--filter", &format!("name=^{name}$")])
+            .output()
+            .with_context(|| format!("failed to run {} ps", self.binary()))?;
+        Ok(!output.stdout.is_empty())
+    }
+
+    fn create(
+        &self,
+        host: Option<&str>,
+        name: &str,
+        image: &str,
+        config: &ContainerConfig,
+    ) -> Result<String> {
+        let mut cmd = self.command(host);
+        cmd.args(["run", "-d", "--name", name]);
+        cmd.args(self.extra_run_args());
+        cmd.args(["-w", &config.working_dir]);
+
+        for volume in &config.volumes {
+            cmd.args(["-v", volume]);
+        }
+        for named_volume in &config.named_volumes {
+            cmd.args(["-v", named_volume]);
+        }
+        for env in &config.environment {
+            cmd.args(["-e", env]);
+        }
+        if let Some(cpu_limit) = &config.cpu_limit {
+            cmd.args(["--cpus", cpu_limit]);
+        }
+        if let Some(memory_limit) = &config.memory_limit {
+            cmd.args(["--memory", memory_limit]);
+        }
+
+        cmd.args(["--network", &config.network_mode.as_flag_value()]);
+        if let Some(pids_limit) = config.pids_limit {
+            cmd.args(["--pids-limit", &pids_limit.to_string()]);
+        }
+        if config.read_only_rootfs {
+            cmd.args(["--read-only", "--tmpfs", "/tmp"]);
+        }
+        for capability in &config.dropped_capabilities {
+            cmd.args(["--cap-drop", capability]);
+        }
+
+        cmd.arg(image);
+        cmd.args(["sleep", "infinity"]);
+
+        let output = cmd
+            .output()
+            .with_context(|| format!("failed to run {} run", self.binary()))?;
+        if !output.status.success() {
+            bail!(
+                "{} run failed: {}",
+                self.binary(),
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn stop(&self, host: Option<&str>, name: &str) -> Result<()> {
+        let output = self
+            .command(host)
+            .args(["stop", name])
+            .output()
+            .with_context(|| format!("failed to run {} stop", self.binary()))?;
+        if !output.status.success() {
+            bail!(
+                "{} stop failed: {}",
+                self.binary(),
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        Ok(())
+    }
+
+    fn remove(&self, host: Option<&str>, name: &str, force: bool) -> Result<()> {
+        let mut cmd = self.command(host);
+        cmd.arg("rm");
+        if force {
+            cmd.arg("-f");
+        }
+        cmd.arg(name);
+
+        let output = cmd
+            .output()
+            .with_context(|| format!("failed to run {} rm", self.binary()))?;
+        if !output.status.success() {
+            bail!(
+                "{} rm failed: {}",
+                self.binary(),
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        Ok(())
+    }
+
+    fn build_image(
+        &self,
+        host: Option<&str>,
+        docker_dir: &Path,
+        tag: &str,
+        label: &str,
+        build_args: &[(String, String)],
+    ) -> Result<()> {
+        let mut cmd = self.command(host);
+        cmd.args(["build", "-t", tag, "--label", label]);
+        for (key, value) in build_args {
+            cmd.args(["--build-arg", &format!("{key}={value}")]);
+        }
+        cmd.arg(docker_dir);
+
+        let status = cmd
+            .status()
+            .with_context(|| format!("failed to run {} build", self.binary()))?;
+        if !status.success() {
+            bail!("{} build failed", self.binary());
+        }
+        Ok(())
+    }
+
+    /// Runs `args` in a throwaway container from `image` and returns its
+    /// trimmed stdout. Used to ask a freshly built image what version of a
+    /// tool it actually installed.
+    fn exec_in_image(&self, host: Option<&str>, image: &str, args: &[&str]) -> Result<String> {
+        let output = self
+            .command(host)
+            .args(["run", "--rm", image])
+            .args(args)
+            .output()
+            .with_context(|| format!("failed to run {} run", self.binary()))?;
+        if !output.status.success() {
+            bail!(
+                "{} run failed: {}",
+                self.binary(),
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn image_label(&self, host: Option<&str>, image: &str, key: &str) -> Result<Option<String>> {
+        let output = self
+            .command(host)
+            .args([
+                "inspect",
+                "--format",
+                &format!("{{{{index .Config.Labels \"{key}\"}}}}"),
+                image,
+            ])
+            .output()
+            .with_context(|| format!("failed to run {} inspect", self.binary()))?;
+
+        if !output.status.success() {
+            // Most likely the image doesn't exist yet.
+            return Ok(None);
+        }
+
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok(if value.is_empty() { None } else { Some(value) })
+    }
+
+    fn list_named_volumes(&self, host: Option<&str>) -> Result<Vec<String>> {
+        let output = self
+            .command(host)
+            .args(["volume", "ls", "--format", "{{.Name}}"])
+            .output()
+            .with_context(|| format!("failed to run {} volume ls", self.binary()))?;
+        if !output.status.success() {
+            bail!(
+                "{} volume ls failed: {}",
+                self.binary(),
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|name| name.starts_with(VOLUME_PREFIX))
+            .map(String::from)
+            .collect())
+    }
+
+    fn ensure_named_volume(&self, host: Option<&str>, name: &str) -> Result<()> {
+        let output = self
+            .command(host)
+            .args(["volume", "inspect", name])
+            .output()
+            .with_context(|| format!("failed to run {} volume inspect", self.binary()))?;
+        if output.status.success() {
+            return Ok(());
+        }
+
+        let output = self
+            .command(host)
+            .args(["volume", "create", name])
+            .output()
+            .with_context(|| format!("failed to run {} volume create", self.binary()))?;
+        if !output.status.success() {
+            bail!(
+                "{} volume create failed: {}",
+                self.binary(),
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        Ok(())
+    }
+
+    fn remove_named_volume(&self, host: Option<&str>, name: &str) -> Result<()> {
+        let output = self
+            .command(host)
+            .args(["volume", "rm", name])
+            .output()
+            .with_context(|| format!("failed to run {} volume rm", self.binary()))?;
+        if !output.status.success() {
+            bail!(
+                "{} volume rm failed: {}",
+                self.binary(),
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        Ok(())
+    }
+
+    /// Names of all containers (running or not) whose name looks like a
+    /// sandbox container, i.e. matches `aoe-sandbox-*`.
+    fn sandbox_container_names(&self, host: Option<&str>) -> Result<Vec<String>> {
+        let output = self
+            .command(host)
+            .args([
+                "ps",
+                "-a",
+                "--filter",
+                "name=aoe-sandbox-",
+                "--format",
+                "{{.Names}}",
+            ])
+            .output()
+            .with_context(|| format!("failed to run {} ps", self.binary()))?;
+        if !output.status.success() {
+            bail!(
+                "{} ps failed: {}",
+                self.binary(),
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .map(String::from)
+            .collect())
+    }
+
+    /// Names of the volumes currently mounted by `container`.
+    fn container_volume_mounts(&self, host: Option<&str>, container: &str) -> Result<Vec<String>> {
+        let output = self
+            .command(host)
+            .args([
+                "inspect",
+                "--format",
+                "{{range .Mounts}}{{if eq .Type \"volume\"}}{{.Name}}\n{{end}}{{end}}",
+                container,
+            ])
+            .output()
+            .with_context(|| format!("failed to run {} inspect", self.binary()))?;
+        if !output.status.success() {
+            bail!(
+                "{} inspect failed: {}",
+                self.binary(),
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(String::from)
+            .collect())
+    }
+}
+
+/// The default backend: a local or remote Docker daemon.
+pub struct DockerRuntime;
+
+impl ContainerRuntime for DockerRuntime {
+    fn binary(&self) -> &'static str {
+        "docker"
+    }
+}
+
+/// Rootless/daemonless Podman.
+pub struct PodmanRuntime;
+
+impl ContainerRuntime for PodmanRuntime {
+    fn binary(&self) -> &'static str {
+        "podman"
+    }
+
+    fn host_flag(&self) -> &'static str {
+        "--url"
+    }
+
+    fn extra_run_args(&self) -> Vec<String> {
+        // Podman defaults to rootless; `cgroupfs` works without a running
+        // systemd user session, which `systemd` (Podman's own default)
+        // requires.
+        vec!["--cgroup-manager".into(), "cgroupfs".into()]
+    }
+}
+
+/// Selects a `ContainerRuntime`, preferring `explicit` (e.g. the `--runtime`
+/// CLI flag), then the `runtime` field in `~/.aoe/config.json`, then the
+/// `AOE_RUNTIME` environment variable, defaulting to Docker.
+pub fn select_runtime(explicit: Option<&str>) -> Box<dyn ContainerRuntime> {
+    let choice = explicit
+        .map(str::to_string)
+        .or_else(|| crate::config::load_settings().ok().and_then(|s| s.runtime))
+        .or_else(|| std::env::var("AOE_RUNTIME").ok());
+
+    match choice.as_deref() {
+        Some("podman") => Box::new(PodmanRuntime),
+        _ => Box::new(DockerRuntime),
+    }
+}
+
+/// Returns the engine host to target, preferring an explicit
+/// `--docker-host` flag over the `DOCKER_HOST` environment variable.
+pub fn resolve_docker_host(explicit: Option<&str>) -> Option<String> {
+    explicit
+        .map(str::to_string)
+        .or_else(|| std::env::var("DOCKER_HOST").ok())
+}
+
+/// Whether the selected runtime's CLI is installed.
+pub fn is_docker_available() -> bool {
+    select_runtime(None).is_available()
+}
+
+/// Whether the selected runtime's daemon (if it has one) is reachable.
+pub fn is_daemon_running() -> bool {
+    select_runtime(None).is_daemon_running()
+}
+
+/// A handle to a named sandbox container, addressed by its derived name.
+pub struct DockerContainer {
+    name: String,
+    image: String,
+    host: Option<String>,
+    runtime: Box<dyn ContainerRuntime>,
+}
+
+impl DockerContainer {
+    pub fn new(session_id: &str, image: &str) -> Self {
+        Self {
+            name: Self::generate_name(session_id),
+            image: image.to_string(),
+            host: resolve_docker_host(None),
+            runtime: select_runtime(None),
+        }
+    }
+
+    /// Same as `new`, but targeting an explicit remote engine.
+    pub fn with_docker_host(session_id: &str, image: &str, docker_host: Option<&str>) -> Self {
+        Self {
+            name: Self::generate_name(session_id),
+            image: image.to_string(),
+            host: resolve_docker_host(docker_host),
+            runtime: select_runtime(None),
+        }
+    }
+
+    /// Same as `new`, but targeting an explicit runtime backend (Docker,
+    /// Podman, ...) and engine host.
+    pub fn with_runtime(
+        session_id: &str,
+        image: &str,
+        runtime: Box<dyn ContainerRuntime>,
+        docker_host: Option<&str>,
+    ) -> Self {
+        Self {
+            name: Self::generate_name(session_id),
+            image: image.to_string(),
+            host: resolve_docker_host(docker_host),
+            runtime,
+        }
+    }
+
+    pub fn generate_name(session_id: &str) -> String {
+        let short = &session_id[..session_id.len().min(8)];
+        format!("aoe-sandbox-{short}")
+    }
+
+    pub fn exists(&self) -> Result<bool> {
+        self.runtime.exists(self.host.as_deref(), &self.name)
+    }
+
+    pub fn is_running(&self) -> Result<bool> {
+        self.runtime.is_running(self.host.as_deref(), &self.name)
+    }
+
+    /// Creates and starts the container, returning its container id.
+    pub fn create(&self, config: &ContainerConfig) -> Result<String> {
+        if self.image == SANDBOX_IMAGE {
+            ensure_sandbox_image_fresh(self.runtime.as_ref(), Path::new("docker"), self.host.as_deref())?;
+        }
+
+        for named_volume in &config.named_volumes {
+            let volume_name = named_volume.split(':').next().unwrap_or(named_volume);
+            self.runtime.ensure_named_volume(self.host.as_deref(), volume_name)?;
+        }
+
+        self.runtime
+            .create(self.host.as_deref(), &self.name, &self.image, config)
+    }
+
+    pub fn stop(&self) -> Result<()> {
+        self.runtime.stop(self.host.as_deref(), &self.name)
+    }
+
+    pub fn remove(&self, force: bool) -> Result<()> {
+        self.runtime.remove(self.host.as_deref(), &self.name, force)
+    }
+}
+
+/// Lists the names of every `aoe-cache-*` volume on the target engine.
+pub fn list_named_volumes(runtime: &dyn ContainerRuntime, docker_host: Option<&str>) -> Result<Vec<String>> {
+    runtime.list_named_volumes(docker_host)
+}
+
+pub fn ensure_named_volume(name: &str, docker_host: Option<&str>) -> Result<()> {
+    select_runtime(None).ensure_named_volume(docker_host, name)
+}
+
+pub fn remove_named_volume(
+    runtime: &dyn ContainerRuntime,
+    name: &str,
+    docker_host: Option<&str>,
+) -> Result<()> {
+    runtime.remove_named_volume(docker_host, name)
+}
+
+/// Removes every `aoe-cache-*` volume that isn't mounted by any `aoe-sandbox-*`
+/// container, returning the names of the volumes that were removed.
+pub fn prune_orphaned_volumes(
+    runtime: &dyn ContainerRuntime,
+    docker_host: Option<&str>,
+) -> Result<Vec<String>> {
+    let volumes = runtime.list_named_volumes(docker_host)?;
+
+    let mut in_use = std::collections::HashSet::new();
+    for container in runtime.sandbox_container_names(docker_host)? {
+        in_use.extend(runtime.container_volume_mounts(docker_host, &container)?);
+    }
+
+    let mut removed = Vec::new();
+    for volume in orphaned_volumes(&volumes, &in_use) {
+        runtime.remove_named_volume(docker_host, &volume)?;
+        removed.push(volume);
+    }
+
+    Ok(removed)
+}
+
+/// Cross-references `volumes` (every named volume aoe manages) against
+/// `in_use` (the volumes still mounted by a sandbox container), returning
+/// the ones no container references anymore.
+fn orphaned_volumes(volumes: &[String], in_use: &std::collections::HashSet<String>) -> Vec<String> {
+    volumes
+        .iter()
+        .filter(|volume| !in_use.contains(*volume))
+        .cloned()
+        .collect()
+}
+
+/// Hashes the Dockerfile in `docker_dir` together with the declared tool
+/// set and the pinned versions in `docker/tools.lock`, so a change to any
+/// of the three invalidates a previously built sandbox image. Folding the
+/// lockfile in means bumping a pinned version (the exact case
+/// `docker/tools.lock` exists to make auditable) forces a rebuild instead
+/// of silently reusing the stale image.
+pub fn compute_dockerfile_hash(docker_dir: &Path) -> Result<String> {
+    let dockerfile = docker_dir.join("Dockerfile");
+    let contents = std::fs::read(&dockerfile)
+        .with_context(|| format!("failed to read {}", dockerfile.display()))?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(&contents);
+    for tool in SANDBOX_TOOLS {
+        hasher.update(tool.name.as_bytes());
+        hasher.update([0u8]);
+    }
+
+    let lock = load_tools_lock(docker_dir)?;
+    for (name, version) in &lock.versions {
+        hasher.update(name.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(version.as_bytes());
+        hasher.update([0u8]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Reads the dockerfile-hash label off `image`, if the image exists.
+pub fn image_dockerfile_hash(
+    runtime: &dyn ContainerRuntime,
+    image: &str,
+    docker_host: Option<&str>,
+) -> Result<Option<String>> {
+    runtime.image_label(docker_host, image, DOCKERFILE_HASH_LABEL)
+}
+
+/// Builds the sandbox image from `docker_dir` (which must contain a
+/// `Dockerfile`), pinning tool versions from `docker/tools.lock` when one
+/// exists, and streaming build progress to the terminal.
+///
+/// Tools without a `version_build_arg` (e.g. `claude`, `opencode`) always
+/// install whatever is latest upstream, so the version actually installed
+/// can only be known after the build runs. The hash stamped on the image
+/// has to reflect that post-build lock state too, or `tools.lock` would
+/// keep drifting out from under an already-stamped hash and
+/// `ensure_sandbox_image_fresh` would rebuild on every call. So this runs
+/// in two passes: build once to resolve versions and update the lock, then
+/// rebuild (a cache hit, so cheap) to stamp the hash computed from that
+/// updated lock.
+pub fn build_sandbox_image(
+    runtime: &dyn ContainerRuntime,
+    docker_dir: &Path,
+    docker_host: Option<&str>,
+) -> Result<()> {
+    let lock = load_tools_lock(docker_dir)?;
+    let build_args: Vec<(String, String)> = SANDBOX_TOOLS
+        .iter()
+        .filter_map(|tool| {
+            let build_arg = tool.version_build_arg?;
+            let version = lock.versions.get(tool.name)?;
+            Some((build_arg.to_string(), version.clone()))
+        })
+        .collect();
+
+    let pending_label = format!("{DOCKERFILE_HASH_LABEL}=pending");
+    runtime.build_image(docker_host, docker_dir, SANDBOX_IMAGE, &pending_label, &build_args)?;
+
+    let mut lock = lock;
+    for tool in SANDBOX_TOOLS {
+        let version = runtime.exec_in_image(docker_host, SANDBOX_IMAGE, tool.version_cmd)?;
+        lock.versions.insert(tool.name.to_string(), version);
+    }
+    save_tools_lock(docker_dir, &lock)?;
+
+    let hash = compute_dockerfile_hash(docker_dir)?;
+    let label = format!("{DOCKERFILE_HASH_LABEL}={hash}");
+    runtime.build_image(docker_host, docker_dir, SANDBOX_IMAGE, &label, &build_args)?;
+
+    Ok(())
+}
+
+/// Rebuilds the sandbox image if it's missing or stale relative to the
+/// current Dockerfile/tool set. Called before starting any sandboxed
+/// `Instance` so a changed Dockerfile is never silently ignored.
+pub fn ensure_sandbox_image_fresh(
+    runtime: &dyn ContainerRuntime,
+    docker_dir: &Path,
+    docker_host: Option<&str>,
+) -> Result<()> {
+    let current_hash = compute_dockerfile_hash(docker_dir)?;
+    let image_hash = image_dockerfile_hash(runtime, SANDBOX_IMAGE, docker_host)?;
+
+    if image_hash.as_deref() != Some(current_hash.as_str()) {
+        build_sandbox_image(runtime, docker_dir, docker_host)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orphaned_volumes_excludes_in_use() {
+        let volumes = vec![
+            "aoe-cache-npm".to_string(),
+            "aoe-cache-cargo".to_string(),
+            "aoe-cache-stale".to_string(),
+        ];
+        let mut in_use = std::collections::HashSet::new();
+        in_use.insert("aoe-cache-npm".to_string());
+        in_use.insert("aoe-cache-cargo".to_string());
+
+        let orphaned = orphaned_volumes(&volumes, &in_use);
+
+        assert_eq!(orphaned, vec!["aoe-cache-stale".to_string()]);
+    }
+
+    #[test]
+    fn orphaned_volumes_empty_when_all_in_use() {
+        let volumes = vec!["aoe-cache-npm".to_string()];
+        let mut in_use = std::collections::HashSet::new();
+        in_use.insert("aoe-cache-npm".to_string());
+
+        assert!(orphaned_volumes(&volumes, &in_use).is_empty());
+    }
+
+    #[test]
+    fn dockerfile_hash_is_stable_for_identical_contents() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("Dockerfile"), "FROM ubuntu:22.04\n").unwrap();
+
+        let first = compute_dockerfile_hash(dir.path()).unwrap();
+        let second = compute_dockerfile_hash(dir.path()).unwrap();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn dockerfile_hash_changes_with_dockerfile_contents() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("Dockerfile"), "FROM ubuntu:22.04\n").unwrap();
+        let before = compute_dockerfile_hash(dir.path()).unwrap();
+
+        std::fs::write(dir.path().join("Dockerfile"), "FROM ubuntu:24.04\n").unwrap();
+        let after = compute_dockerfile_hash(dir.path()).unwrap();
+
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn select_runtime_honors_explicit_choice() {
+        assert_eq!(select_runtime(Some("docker")).binary(), "docker");
+        assert_eq!(select_runtime(Some("podman")).binary(), "podman");
+    }
+
+    #[test]
+    fn select_runtime_defaults_to_docker_for_unknown_choice() {
+        assert_eq!(select_runtime(Some("unknown")).binary(), "docker");
+    }
+
+    #[test]
+    fn network_mode_flag_values() {
+        assert_eq!(NetworkMode::None.as_flag_value(), "none");
+        assert_eq!(NetworkMode::Bridge.as_flag_value(), "bridge");
+        assert_eq!(NetworkMode::Named("aoe-net".to_string()).as_flag_value(), "aoe-net");
+    }
+}
@@ -0,0 +1,7 @@
+//! agent-of-empires: a terminal dashboard for running and orchestrating coding agents.
+
+pub mod cli;
+pub mod config;
+pub mod docker;
+pub mod session;
+pub mod sound;
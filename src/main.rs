@@ -0,0 +1,10 @@
+use anyhow::Result;
+use clap::Parser;
+
+use agent_of_empires::cli::{self, Cli};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    cli::run(cli).await
+}
@@ -0,0 +1,84 @@
+//! Session persistence: tracked agent instances and their sandbox state.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::docker::NetworkMode;
+
+/// Sandbox container metadata attached to a sandboxed `Instance`.
+///
+/// This is persisted alongside the instance so that container state
+/// (and the isolation policy it was created with) survives across
+/// `aoe` invocations and process restarts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SandboxInfo {
+    pub enabled: bool,
+    pub container_id: Option<String>,
+    pub image: Option<String>,
+    pub container_name: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub yolo_mode: Option<bool>,
+    /// The network access policy the container was created with, so it
+    /// persists and is auditable across save/load rather than only living
+    /// in the `ContainerConfig` used at creation time.
+    pub network_mode: NetworkMode,
+    /// Whether the container's rootfs was mounted read-only.
+    pub read_only_rootfs: bool,
+}
+
+/// A single tracked agent session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Instance {
+    pub name: String,
+    pub working_dir: String,
+    pub sandbox_info: Option<SandboxInfo>,
+}
+
+impl Instance {
+    pub fn new(name: impl Into<String>, working_dir: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            working_dir: working_dir.into(),
+            sandbox_info: None,
+        }
+    }
+
+    /// Whether this instance is currently running inside a sandbox container.
+    pub fn is_sandboxed(&self) -> bool {
+        self.sandbox_info.as_ref().is_some_and(|info| info.enabled)
+    }
+}
+
+/// Loads and saves the set of tracked instances for a given storage namespace.
+pub struct Storage {
+    path: PathBuf,
+}
+
+impl Storage {
+    pub fn new(namespace: &str) -> Result<Self> {
+        let home = dirs::home_dir().context("could not determine home directory")?;
+        let dir = home.join(".aoe").join("state");
+        fs::create_dir_all(&dir).context("failed to create state directory")?;
+        Ok(Self {
+            path: dir.join(format!("{namespace}.json")),
+        })
+    }
+
+    pub fn save(&self, instances: &[Instance]) -> Result<()> {
+        let json = serde_json::to_string_pretty(instances)?;
+        fs::write(&self.path, json).context("failed to write session state")?;
+        Ok(())
+    }
+
+    pub fn load(&self) -> Result<Vec<Instance>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let json = fs::read_to_string(&self.path).context("failed to read session state")?;
+        let instances = serde_json::from_str(&json)?;
+        Ok(instances)
+    }
+}
@@ -0,0 +1,273 @@
+//! Sound effect management: bundled CC0 sounds and user-installed packs.
+
+use anyhow::{anyhow, bail, Context, Result};
+use flate2::read::GzDecoder;
+use indicatif::{ProgressBar, ProgressStyle};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const BUNDLED_SOUNDS: &[(&str, &[u8])] = &[];
+
+/// Directory sounds are installed into, e.g. `~/.config/aoe/sounds`.
+pub fn get_sounds_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("aoe").join("sounds"))
+}
+
+pub fn install_bundled_sounds() -> Result<()> {
+    let dir = get_sounds_dir().context("could not determine sounds directory")?;
+    fs::create_dir_all(&dir).context("failed to create sounds directory")?;
+
+    for (name, bytes) in BUNDLED_SOUNDS {
+        fs::write(dir.join(format!("{name}.wav")), bytes)
+            .with_context(|| format!("failed to install bundled sound '{name}'"))?;
+    }
+
+    Ok(())
+}
+
+pub fn list_available_sounds() -> Vec<String> {
+    let Some(dir) = get_sounds_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut sounds: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            let ext = path.extension()?.to_str()?;
+            if ext.eq_ignore_ascii_case("wav") || ext.eq_ignore_ascii_case("ogg") {
+                path.file_stem()?.to_str().map(String::from)
+            } else {
+                None
+            }
+        })
+        .collect();
+    sounds.sort();
+    sounds
+}
+
+pub fn play_sound(name: &str) {
+    let Some(dir) = get_sounds_dir() else { return };
+
+    for ext in ["wav", "ogg"] {
+        let path = dir.join(format!("{name}.{ext}"));
+        if path.exists() {
+            if let Err(err) = play_file(&path) {
+                eprintln!("⚠️  Failed to play sound: {err}");
+            }
+            return;
+        }
+    }
+}
+
+/// Downloads a community sound pack (a `.tar.gz` of `.wav`/`.ogg` files) from
+/// `url` and extracts it into the sounds directory, returning the names of
+/// the sounds that were installed.
+///
+/// Entries with an absolute path or a `..` component are rejected as
+/// traversal attempts. Entries that collide with an already-installed sound
+/// are skipped unless `force` is set.
+pub async fn install_from_url(url: &str, force: bool) -> Result<Vec<String>> {
+    let dir = get_sounds_dir().context("could not determine sounds directory")?;
+    fs::create_dir_all(&dir).context("failed to create sounds directory")?;
+
+    let response = reqwest::get(url)
+        .await
+        .with_context(|| format!("failed to fetch sound pack from '{url}'"))?
+        .error_for_status()
+        .with_context(|| format!("'{url}' returned an error response"))?;
+
+    let total_bytes = response.content_length();
+    let progress = ProgressBar::new(total_bytes.unwrap_or(0));
+    if total_bytes.is_some() {
+        progress.set_style(
+            ProgressStyle::with_template(
+                "{bar:40.cyan/blue} {bytes}/{total_bytes} ({eta})",
+            )
+            .unwrap(),
+        );
+    } else {
+        progress.set_style(ProgressStyle::with_template("{bytes} downloaded").unwrap());
+    }
+
+    let mut bytes = Vec::new();
+    let mut stream = response.bytes_stream();
+    use futures_util::StreamExt;
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.context("error while downloading sound pack")?;
+        bytes.extend_from_slice(&chunk);
+        progress.inc(chunk.len() as u64);
+    }
+    progress.finish_and_clear();
+
+    let installed_before = list_available_sounds();
+    extract_sound_pack(&bytes, &dir, &installed_before, force)
+}
+
+/// Extracts the `.wav`/`.ogg` entries of a `.tar.gz` sound pack into `dir`,
+/// returning the names installed. Rejects entries with an absolute path or
+/// a `..` component, and entries that aren't regular files (symlinks and
+/// hardlinks can point anywhere on the filesystem regardless of the entry's
+/// own path), before writing anything. Entries that collide with a name in
+/// `installed_before` are skipped unless `force` is set.
+fn extract_sound_pack(
+    bytes: &[u8],
+    dir: &Path,
+    installed_before: &[String],
+    force: bool,
+) -> Result<Vec<String>> {
+    let mut installed = Vec::new();
+
+    let decoder = GzDecoder::new(bytes);
+    let mut archive = tar::Archive::new(decoder);
+    for entry in archive.entries().context("failed to read sound pack archive")? {
+        let mut entry = entry.context("failed to read sound pack entry")?;
+        let entry_path = entry
+            .path()
+            .context("invalid entry path in sound pack")?
+            .into_owned();
+
+        if entry_path.is_absolute() || entry_path.components().any(|c| c == std::path::Component::ParentDir) {
+            bail!("sound pack entry '{}' is not a safe path", entry_path.display());
+        }
+
+        if entry.header().entry_type() != tar::EntryType::Regular {
+            bail!(
+                "sound pack entry '{}' is not a regular file",
+                entry_path.display()
+            );
+        }
+
+        let Some(ext) = entry_path.extension().and_then(|e| e.to_str()) else {
+            continue;
+        };
+        if !ext.eq_ignore_ascii_case("wav") && !ext.eq_ignore_ascii_case("ogg") {
+            continue;
+        }
+
+        let name = entry_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .ok_or_else(|| anyhow!("sound pack entry '{}' has no file name", entry_path.display()))?
+            .to_string();
+
+        if installed_before.contains(&name) && !force {
+            continue;
+        }
+
+        let dest = dir.join(entry_path.file_name().unwrap());
+        entry
+            .unpack(&dest)
+            .with_context(|| format!("failed to extract '{}'", entry_path.display()))?;
+        installed.push(name);
+    }
+
+    Ok(installed)
+}
+
+fn play_file(path: &Path) -> Result<()> {
+    let (_stream, stream_handle) = rodio::OutputStream::try_default()?;
+    let sink = rodio::Sink::try_new(&stream_handle)?;
+    let file = std::io::BufReader::new(fs::File::open(path)?);
+    sink.append(rodio::Decoder::new(file)?);
+    sink.sleep_until_end();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    fn make_tar_gz(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        for (path, contents) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            // Write the path directly into the header, bypassing
+            // `set_path`'s `..`/absolute-path rejection, so traversal
+            // entries can actually be constructed to test against.
+            let name = header.as_old_mut().name.as_mut();
+            name[..path.len()].copy_from_slice(path.as_bytes());
+            header.set_cksum();
+            builder.append(&header, *contents).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    #[test]
+    fn rejects_parent_dir_traversal() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let archive = make_tar_gz(&[("../evil.wav", b"noise")]);
+
+        let result = extract_sound_pack(&archive, temp.path(), &[], false);
+
+        assert!(result.is_err());
+        assert!(!temp.path().parent().unwrap().join("evil.wav").exists());
+    }
+
+    #[test]
+    fn rejects_absolute_path() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let archive = make_tar_gz(&[("/etc/evil.wav", b"noise")]);
+
+        let result = extract_sound_pack(&archive, temp.path(), &[], false);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rejects_symlink_entries() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let target = temp.path().join("outside-target");
+        std::fs::write(&target, b"secret").unwrap();
+
+        let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        header.set_mode(0o644);
+        header.set_path("drum.wav").unwrap();
+        header.set_link_name(&target).unwrap();
+        header.set_cksum();
+        builder.append(&header, std::io::empty()).unwrap();
+        let archive = builder.into_inner().unwrap().finish().unwrap();
+
+        let dest_dir = temp.path().join("sounds");
+        std::fs::create_dir_all(&dest_dir).unwrap();
+        let result = extract_sound_pack(&archive, &dest_dir, &[], false);
+
+        assert!(result.is_err());
+        assert!(!dest_dir.join("drum.wav").exists());
+    }
+
+    #[test]
+    fn installs_safe_entries() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let archive = make_tar_gz(&[("drum.wav", b"noise"), ("notes.txt", b"ignored")]);
+
+        let installed = extract_sound_pack(&archive, temp.path(), &[], false).unwrap();
+
+        assert_eq!(installed, vec!["drum".to_string()]);
+        assert!(temp.path().join("drum.wav").exists());
+        assert!(!temp.path().join("notes.txt").exists());
+    }
+
+    #[test]
+    fn skips_already_installed_unless_forced() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let archive = make_tar_gz(&[("drum.wav", b"noise")]);
+
+        let installed = extract_sound_pack(&archive, temp.path(), &["drum".to_string()], false).unwrap();
+        assert!(installed.is_empty());
+
+        let archive = make_tar_gz(&[("drum.wav", b"noise")]);
+        let installed = extract_sound_pack(&archive, temp.path(), &["drum".to_string()], true).unwrap();
+        assert_eq!(installed, vec!["drum".to_string()]);
+    }
+}
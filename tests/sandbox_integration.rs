@@ -6,7 +6,7 @@
 //! - Docker availability validation
 //! - Tool availability in the sandbox image
 
-use agent_of_empires::docker::{is_daemon_running, is_docker_available, DockerContainer};
+use agent_of_empires::docker::{is_daemon_running, is_docker_available, DockerContainer, NetworkMode};
 use agent_of_empires::session::{Instance, SandboxInfo, Storage};
 use std::path::PathBuf;
 
@@ -49,6 +49,10 @@ fn dockerfile_path() -> PathBuf {
         .join("Dockerfile")
 }
 
+fn docker_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("docker")
+}
+
 /// Static test: Verify Dockerfile contains install commands for all supported tools.
 /// This test doesn't require Docker and should always run.
 #[test]
@@ -96,6 +100,18 @@ fn test_all_available_tools_have_sandbox_entries() {
             tool_name
         );
     }
+
+    let lock = agent_of_empires::docker::load_tools_lock(&docker_dir())
+        .expect("failed to read docker/tools.lock");
+
+    for tool_name in &available_tool_names {
+        assert!(
+            lock.versions.contains_key(*tool_name),
+            "Tool '{}' is in AvailableTools but has no pinned version in docker/tools.lock.\n\
+             Run 'aoe sandbox build' to resolve and record its installed version.",
+            tool_name
+        );
+    }
 }
 
 /// Runtime test: Verify all tools are actually executable in the sandbox container.
@@ -157,6 +173,8 @@ fn test_sandbox_info_serialization() {
         container_name: "aoe-sandbox-test1234".to_string(),
         created_at: Some(chrono::Utc::now()),
         yolo_mode: None,
+        network_mode: NetworkMode::None,
+        read_only_rootfs: true,
     };
 
     let json = serde_json::to_string(&sandbox_info).unwrap();
@@ -179,6 +197,8 @@ fn test_instance_is_sandboxed() {
         container_name: "aoe-sandbox-test".to_string(),
         created_at: None,
         yolo_mode: None,
+        network_mode: NetworkMode::None,
+        read_only_rootfs: true,
     });
     assert!(inst.is_sandboxed());
 
@@ -189,6 +209,8 @@ fn test_instance_is_sandboxed() {
         container_name: "aoe-sandbox-test".to_string(),
         created_at: None,
         yolo_mode: None,
+        network_mode: NetworkMode::None,
+        read_only_rootfs: true,
     });
     assert!(!inst.is_sandboxed());
 }
@@ -208,6 +230,8 @@ fn test_sandbox_info_persists_across_save_load() {
         container_name: "aoe-sandbox-abcd1234".to_string(),
         created_at: Some(chrono::Utc::now()),
         yolo_mode: Some(true),
+        network_mode: NetworkMode::Bridge,
+        read_only_rootfs: false,
     });
 
     storage.save(&[inst.clone()]).unwrap();
@@ -259,11 +283,7 @@ fn test_container_lifecycle() {
 
     let config = agent_of_empires::docker::ContainerConfig {
         working_dir: "/workspace".to_string(),
-        volumes: vec![],
-        named_volumes: vec![],
-        environment: vec![],
-        cpu_limit: None,
-        memory_limit: None,
+        ..Default::default()
     };
 
     let container_id = container.create(&config).unwrap();
@@ -299,11 +319,7 @@ fn test_container_force_remove() {
 
     let config = agent_of_empires::docker::ContainerConfig {
         working_dir: "/workspace".to_string(),
-        volumes: vec![],
-        named_volumes: vec![],
-        environment: vec![],
-        cpu_limit: None,
-        memory_limit: None,
+        ..Default::default()
     };
 
     container.create(&config).unwrap();